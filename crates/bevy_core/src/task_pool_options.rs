@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use bevy_tasks::{
-    AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool, TaskPoolBuilder, TaskPoolThreadPanicPolicy,
+    AsyncComputeTaskPool, ComputeTaskPool, CoreAffinity, IoTaskPool, TaskPoolBuilder,
+    TaskPoolThreadPanicPolicy, ThreadPriority,
 };
 use bevy_utils::tracing::trace;
 
@@ -7,13 +10,17 @@ use bevy_utils::tracing::trace;
 /// and number of total cores
 #[derive(Clone)]
 pub struct TaskPoolThreadAssignmentPolicy {
-    /// Force using at least this many threads
+    /// Force using at least this many threads. These stay resident even while the pool is idle.
     pub min_threads: usize,
     /// Under no circumstance use more than this many threads for this pool
     pub max_threads: usize,
     /// Target using this percentage of total cores, clamped by min_threads and max_threads. It is
     /// permitted to use 1.0 to try to use all remaining threads
     pub percent: f32,
+    /// How long an on-demand worker may sit idle before retiring itself. Only workers above
+    /// `min_threads` retire, so the pool keeps `min_threads` resident and bursts back towards the
+    /// resolved thread count on demand.
+    pub idle_timeout: Duration,
 }
 
 impl TaskPoolThreadAssignmentPolicy {
@@ -39,6 +46,17 @@ pub struct TaskPoolPolicies {
     pub assignment_policy: TaskPoolThreadAssignmentPolicy,
     /// Used to determine the panic policy of the task pool
     pub panic_policy: TaskPoolThreadPanicPolicy,
+    /// Bounds the pool's work queue to `resolved_thread_count * buffer_multiplier` slots. Once the
+    /// queue is full, `spawn` blocks the caller until a slot frees (backpressure) and `try_spawn`
+    /// sheds the job, keeping memory predictable when a system enqueues huge batches (e.g. asset
+    /// streaming on the IO pool).
+    pub buffer_multiplier: usize,
+    /// OS priority applied to this pool's worker threads, if any. `None` inherits the spawning
+    /// thread's priority.
+    pub thread_priority: Option<ThreadPriority>,
+    /// Core-affinity strategy for this pool's worker threads, if any. `None` lets the OS scheduler
+    /// place workers freely.
+    pub core_affinity: Option<CoreAffinity>,
 }
 
 /// Helper for configuring and creating the default task pools. For end-users who want full control,
@@ -74,8 +92,14 @@ impl Default for DefaultTaskPoolOptions {
                     min_threads: 1,
                     max_threads: 4,
                     percent: 0.25,
+                    // IO sits idle most of a frame, so retire burst workers quickly
+                    idle_timeout: Duration::from_secs(2),
                 },
                 panic_policy: TaskPoolThreadPanicPolicy::CatchAndIgnore,
+                buffer_multiplier: 8,
+                // Background IO should yield to frame-critical compute
+                thread_priority: Some(ThreadPriority::BelowNormal),
+                core_affinity: Some(CoreAffinity::Float),
             },
 
             async_compute: TaskPoolPolicies {
@@ -84,8 +108,14 @@ impl Default for DefaultTaskPoolOptions {
                     min_threads: 1,
                     max_threads: 4,
                     percent: 0.25,
+                    // Async compute also spends most of a frame idle
+                    idle_timeout: Duration::from_secs(2),
                 },
                 panic_policy: TaskPoolThreadPanicPolicy::Propagate,
+                buffer_multiplier: 8,
+                // Async compute is also background work; let it float below normal
+                thread_priority: Some(ThreadPriority::BelowNormal),
+                core_affinity: Some(CoreAffinity::Float),
             },
 
             compute: TaskPoolPolicies {
@@ -94,8 +124,16 @@ impl Default for DefaultTaskPoolOptions {
                     min_threads: 1,
                     max_threads: std::usize::MAX,
                     percent: 1.0, // This 1.0 here means "whatever is left over"
+                    // Compute is on the frame-critical path; keep burst workers around longer so
+                    // we don't pay spawn cost every frame
+                    idle_timeout: Duration::from_secs(10),
                 },
                 panic_policy: TaskPoolThreadPanicPolicy::Propagate,
+                buffer_multiplier: 8,
+                // Frame-critical compute runs at normal priority, pinned to the first physical
+                // cores so it isn't migrated off mid-frame
+                thread_priority: Some(ThreadPriority::Normal),
+                core_affinity: Some(CoreAffinity::FirstPhysicalCores),
             },
         }
     }
@@ -131,8 +169,15 @@ impl DefaultTaskPoolOptions {
 
             IoTaskPool::init(|| {
                 TaskPoolBuilder::default()
-                    .num_threads(io_threads)
+                    .elastic(
+                        self.io.assignment_policy.min_threads.min(io_threads),
+                        io_threads,
+                        self.io.assignment_policy.idle_timeout,
+                    )
+                    .buffer_capacity(io_threads * self.io.buffer_multiplier)
                     .thread_name("IO Task Pool".to_string())
+                    .thread_priority(self.io.thread_priority)
+                    .core_affinity(self.io.core_affinity)
                     .panic_policy(self.io.panic_policy)
                     .build()
             });
@@ -150,8 +195,18 @@ impl DefaultTaskPoolOptions {
 
             AsyncComputeTaskPool::init(|| {
                 TaskPoolBuilder::default()
-                    .num_threads(async_compute_threads)
+                    .elastic(
+                        self.async_compute
+                            .assignment_policy
+                            .min_threads
+                            .min(async_compute_threads),
+                        async_compute_threads,
+                        self.async_compute.assignment_policy.idle_timeout,
+                    )
+                    .buffer_capacity(async_compute_threads * self.async_compute.buffer_multiplier)
                     .thread_name("Async Compute Task Pool".to_string())
+                    .thread_priority(self.async_compute.thread_priority)
+                    .core_affinity(self.async_compute.core_affinity)
                     .panic_policy(self.async_compute.panic_policy)
                     .build()
             });
@@ -169,11 +224,95 @@ impl DefaultTaskPoolOptions {
 
             ComputeTaskPool::init(|| {
                 TaskPoolBuilder::default()
-                    .num_threads(compute_threads)
+                    .elastic(
+                        self.compute
+                            .assignment_policy
+                            .min_threads
+                            .min(compute_threads),
+                        compute_threads,
+                        self.compute.assignment_policy.idle_timeout,
+                    )
+                    .buffer_capacity(compute_threads * self.compute.buffer_multiplier)
                     .thread_name("Compute Task Pool".to_string())
+                    .thread_priority(self.compute.thread_priority)
+                    .core_affinity(self.compute.core_affinity)
                     .panic_policy(self.compute.panic_policy)
                     .build()
             });
         }
     }
+
+    /// Re-resolve the thread targets and resize the already-initialized default pools in place,
+    /// without tearing them down or restarting the app.
+    ///
+    /// This is useful on platforms where the usable core count changes at runtime (mobile thermal
+    /// throttling, a container's CPU quota being adjusted). It recomputes `total_threads` from the
+    /// current [`logical_core_count`](bevy_tasks::logical_core_count), re-runs each
+    /// `assignment_policy.get_number_of_threads`, and hands the new `(min, max)` bounds and queue
+    /// capacity to each pool via [`TaskPool::resize_elastic`](bevy_tasks::TaskPool::resize_elastic)
+    /// and [`TaskPool::set_buffer_capacity`](bevy_tasks::TaskPool::set_buffer_capacity). Each pool
+    /// spawns or retires workers to match. The bounds and the worker count are atomics, so resizing
+    /// takes no lock that task spawning contends on.
+    ///
+    /// Unlike [`create_default_pools`](Self::create_default_pools) this does nothing for pools that
+    /// have not been initialized yet; an uninitialized pool is left untouched. Call it from a
+    /// system holding the [`DefaultTaskPoolOptions`] resource in response to a
+    /// "core budget changed" event.
+    pub fn reconfigure(&self) {
+        let total_threads =
+            bevy_tasks::logical_core_count().clamp(self.min_total_threads, self.max_total_threads);
+        trace!("Reconfiguring default task pools to {} cores", total_threads);
+
+        let mut remaining_threads = total_threads;
+
+        let io_threads = self
+            .io
+            .assignment_policy
+            .get_number_of_threads(remaining_threads, total_threads);
+        trace!("IO Threads: {}", io_threads);
+        remaining_threads = remaining_threads.saturating_sub(io_threads);
+        if let Some(pool) = IoTaskPool::try_get() {
+            pool.resize_elastic(
+                self.io.assignment_policy.min_threads.min(io_threads),
+                io_threads,
+            );
+            pool.set_buffer_capacity(io_threads * self.io.buffer_multiplier);
+        }
+
+        let async_compute_threads = self
+            .async_compute
+            .assignment_policy
+            .get_number_of_threads(remaining_threads, total_threads);
+        trace!("Async Compute Threads: {}", async_compute_threads);
+        remaining_threads = remaining_threads.saturating_sub(async_compute_threads);
+        if let Some(pool) = AsyncComputeTaskPool::try_get() {
+            pool.resize_elastic(
+                self.async_compute
+                    .assignment_policy
+                    .min_threads
+                    .min(async_compute_threads),
+                async_compute_threads,
+            );
+            pool.set_buffer_capacity(
+                async_compute_threads * self.async_compute.buffer_multiplier,
+            );
+        }
+
+        // Resolved last so that `percent: 1.0` still means "whatever is left over"
+        let compute_threads = self
+            .compute
+            .assignment_policy
+            .get_number_of_threads(remaining_threads, total_threads);
+        trace!("Compute Threads: {}", compute_threads);
+        if let Some(pool) = ComputeTaskPool::try_get() {
+            pool.resize_elastic(
+                self.compute
+                    .assignment_policy
+                    .min_threads
+                    .min(compute_threads),
+                compute_threads,
+            );
+            pool.set_buffer_capacity(compute_threads * self.compute.buffer_multiplier);
+        }
+    }
 }
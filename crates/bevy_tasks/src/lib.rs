@@ -0,0 +1,12 @@
+//! A lightweight thread-pool abstraction used by the rest of the engine to run CPU- and IO-bound
+//! work off the main thread.
+
+mod task_pool;
+mod thread_control;
+mod usages;
+
+pub use task_pool::{Task, TaskPool, TaskPoolBuilder, TaskPoolThreadPanicPolicy};
+pub use thread_control::{CoreAffinity, ThreadPriority};
+pub use usages::{
+    logical_core_count, AsyncComputeTaskPool, ComputeTaskPool, IoTaskPool,
+};
@@ -0,0 +1,623 @@
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::Builder as ThreadBuilder;
+use std::time::Duration;
+
+use crate::thread_control::{self, CoreAffinity, ThreadPriority};
+
+/// Determines what a task pool does when one of its worker threads panics while running a task.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TaskPoolThreadPanicPolicy {
+    /// Catch the panic and keep the worker thread alive. Useful for pools whose tasks are
+    /// best-effort (e.g. IO), where one bad task should not tear down the pool.
+    CatchAndIgnore,
+    /// Let the panic unwind out of the worker, ending that worker thread. The task's handle then
+    /// panics when blocked on, since the task never produces a value.
+    Propagate,
+}
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+struct QueueState {
+    jobs: VecDeque<Job>,
+    shutdown: bool,
+}
+
+/// Shared state backing a [`TaskPool`]. Worker threads hold an `Arc` to this, so the pool can be
+/// cloned cheaply and workers keep running for as long as any handle (or any worker) is alive.
+struct Inner {
+    state: Mutex<QueueState>,
+    /// Signalled whenever a job is pushed or the pool is shutting down.
+    available: Condvar,
+    /// Signalled whenever a worker pops a job, freeing a queue slot for a blocked spawner.
+    drained: Condvar,
+    /// Maximum number of queued-but-not-yet-running jobs; `0` means an unbounded queue. Stored as
+    /// an atomic so it can be retuned at runtime (see [`set_buffer_capacity`](TaskPool::set_buffer_capacity)).
+    /// When the queue is full, [`spawn`](TaskPool::spawn) applies backpressure by blocking the
+    /// caller until a slot frees, and [`try_spawn`](TaskPool::try_spawn) sheds the job instead.
+    capacity: AtomicUsize,
+    /// Number of workers currently parked waiting for work.
+    idle_count: AtomicUsize,
+    /// Number of live worker threads, resident plus on-demand. Kept as an atomic so the spawn hot
+    /// path can decide whether to grow the pool without taking a lock.
+    thread_count: AtomicUsize,
+    /// Lower bound on live workers; these stay resident even while the pool is idle.
+    min_threads: AtomicUsize,
+    /// Upper bound on live workers; the pool bursts up to this under load.
+    max_threads: AtomicUsize,
+    /// How long a worker above `min_threads` waits without work before retiring itself. `None`
+    /// disables retirement (fixed-size pool).
+    idle_timeout: Option<Duration>,
+    panic_policy: TaskPoolThreadPanicPolicy,
+    thread_name: Option<String>,
+    /// OS priority applied to each worker on startup, if set.
+    thread_priority: Option<ThreadPriority>,
+    /// Core-affinity strategy applied to each worker on startup, if set.
+    core_affinity: Option<CoreAffinity>,
+    /// Monotonic id handed to each launched worker, used for per-core affinity pinning.
+    next_worker_id: AtomicUsize,
+}
+
+/// A handle to a value being computed on a [`TaskPool`]. Block on it to retrieve the result, or
+/// [`detach`](Task::detach) it to let the task run to completion in the background.
+pub struct Task<T> {
+    receiver: Receiver<T>,
+}
+
+impl<T> Task<T> {
+    /// Wait for the task to finish and return its output.
+    pub fn block(self) -> T {
+        self.receiver
+            .recv()
+            .expect("task panicked or pool was dropped before the task completed")
+    }
+
+    /// Let the task run in the background, discarding its output.
+    pub fn detach(self) {}
+}
+
+/// A thread pool for running CPU-bound work. Created via [`TaskPoolBuilder`].
+///
+/// Pools may be *elastic*: `min_threads` workers stay resident and extra workers (up to
+/// `max_threads`) are spawned on demand when work is queued and every resident worker is busy,
+/// then retire themselves after sitting idle for `idle_timeout`.
+#[derive(Clone)]
+pub struct TaskPool {
+    inner: Arc<Inner>,
+}
+
+impl TaskPool {
+    fn new(
+        min_threads: usize,
+        max_threads: usize,
+        idle_timeout: Option<Duration>,
+        capacity: Option<usize>,
+        panic_policy: TaskPoolThreadPanicPolicy,
+        thread_name: Option<String>,
+        thread_priority: Option<ThreadPriority>,
+        core_affinity: Option<CoreAffinity>,
+    ) -> Self {
+        let min_threads = min_threads.max(1);
+        let max_threads = max_threads.max(min_threads);
+        let inner = Arc::new(Inner {
+            state: Mutex::new(QueueState {
+                jobs: VecDeque::new(),
+                shutdown: false,
+            }),
+            available: Condvar::new(),
+            drained: Condvar::new(),
+            capacity: AtomicUsize::new(capacity.unwrap_or(0)),
+            idle_count: AtomicUsize::new(0),
+            thread_count: AtomicUsize::new(0),
+            min_threads: AtomicUsize::new(min_threads),
+            max_threads: AtomicUsize::new(max_threads),
+            idle_timeout,
+            panic_policy,
+            thread_name,
+            thread_priority,
+            core_affinity,
+            next_worker_id: AtomicUsize::new(0),
+        });
+
+        let pool = TaskPool { inner };
+        for _ in 0..min_threads {
+            pool.spawn_worker();
+        }
+        pool
+    }
+
+    /// The number of worker threads currently alive (resident plus on-demand).
+    pub fn thread_num(&self) -> usize {
+        self.inner.thread_count.load(Ordering::SeqCst)
+    }
+
+    /// Retarget the pool's `(min, max)` worker bounds in place, without tearing the pool down.
+    ///
+    /// New resident workers are spawned immediately to meet a raised `min`; workers above a
+    /// lowered `max` retire themselves as soon as they next finish or wake and find the queue
+    /// empty. The bounds live in
+    /// atomics, so this never blocks the task-spawning hot path.
+    pub fn resize_elastic(&self, min: usize, max: usize) {
+        let min = min.max(1);
+        let max = max.max(min);
+        self.inner.min_threads.store(min, Ordering::SeqCst);
+        self.inner.max_threads.store(max, Ordering::SeqCst);
+
+        // Grow immediately to the new minimum.
+        loop {
+            let current = self.inner.thread_count.load(Ordering::SeqCst);
+            if current >= min {
+                break;
+            }
+            if self
+                .inner
+                .thread_count
+                .compare_exchange(current, current + 1, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                self.launch_thread();
+            }
+        }
+
+        // Wake parked workers so any now above `max` retire promptly.
+        self.inner.available.notify_all();
+    }
+
+    /// Retune the pool's bounded-queue capacity in place. `0` makes the queue unbounded.
+    pub fn set_buffer_capacity(&self, capacity: usize) {
+        self.inner.capacity.store(capacity, Ordering::SeqCst);
+        // A larger cap may have freed slots for spawners blocked on backpressure.
+        self.inner.drained.notify_all();
+    }
+
+    /// Spawn a task onto the pool, returning a [`Task`] handle to its output.
+    ///
+    /// If the pool has a bounded queue that is currently full, this blocks the calling thread
+    /// (backpressure) until a slot frees, rather than letting the queue grow without bound.
+    pub fn spawn<T, F>(&self, f: F) -> Task<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let job: Job = Box::new(move || {
+            let _ = sender.send(f());
+        });
+
+        let mut state = self.inner.state.lock().unwrap();
+        let capacity = self.inner.capacity.load(Ordering::SeqCst);
+        if capacity > 0 {
+            while state.jobs.len() >= capacity && !state.shutdown {
+                state = self.inner.drained.wait(state).unwrap();
+            }
+        }
+        state.jobs.push_back(job);
+        let queued = state.jobs.len();
+        drop(state);
+
+        self.wake_worker(queued);
+        Task { receiver }
+    }
+
+    /// Try to spawn a task without blocking. Returns `Err(f)` if the pool's queue is full so the
+    /// caller can shed load (drop the work, retry later, or run it inline).
+    pub fn try_spawn<T, F>(&self, f: F) -> Result<Task<T>, F>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let mut state = self.inner.state.lock().unwrap();
+        let capacity = self.inner.capacity.load(Ordering::SeqCst);
+        if capacity > 0 && state.jobs.len() >= capacity {
+            return Err(f);
+        }
+        let (sender, receiver) = channel();
+        let job: Job = Box::new(move || {
+            let _ = sender.send(f());
+        });
+        state.jobs.push_back(job);
+        let queued = state.jobs.len();
+        drop(state);
+
+        self.wake_worker(queued);
+        Ok(Task { receiver })
+    }
+
+    fn wake_worker(&self, queued: usize) {
+        // Grow the pool if there's no idle worker to pick this up and we're below max.
+        self.try_grow(queued);
+        self.inner.available.notify_one();
+    }
+
+    /// Spawn an on-demand worker if there are more queued jobs than parked workers (so some job
+    /// has no free worker to pick it up) and we're still below `max_threads`.
+    fn try_grow(&self, queued: usize) {
+        if self.inner.idle_count.load(Ordering::SeqCst) >= queued {
+            return;
+        }
+        let mut current = self.inner.thread_count.load(Ordering::SeqCst);
+        loop {
+            if current >= self.inner.max_threads.load(Ordering::SeqCst) {
+                return;
+            }
+            match self.inner.thread_count.compare_exchange(
+                current,
+                current + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => {
+                    self.launch_thread();
+                    return;
+                }
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// Increment `thread_count` and launch a worker thread. Used on startup to bring the pool up to
+    /// `min_threads`; on-demand growth goes through [`try_grow`](Self::try_grow) which reserves the
+    /// slot first.
+    fn spawn_worker(&self) {
+        self.inner.thread_count.fetch_add(1, Ordering::SeqCst);
+        self.launch_thread();
+    }
+
+    fn launch_thread(&self) {
+        let inner = Arc::clone(&self.inner);
+        let worker_index = inner.next_worker_id.fetch_add(1, Ordering::SeqCst);
+        let mut builder = ThreadBuilder::new();
+        if let Some(name) = &inner.thread_name {
+            builder = builder.name(name.clone());
+        }
+        builder
+            .spawn(move || {
+                thread_control::apply(inner.thread_priority, inner.core_affinity, worker_index);
+                worker_loop(inner);
+            })
+            .expect("failed to spawn task pool worker thread");
+    }
+}
+
+impl std::fmt::Debug for TaskPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskPool")
+            .field("thread_num", &self.thread_num())
+            .field(
+                "min_threads",
+                &self.inner.min_threads.load(Ordering::SeqCst),
+            )
+            .field(
+                "max_threads",
+                &self.inner.max_threads.load(Ordering::SeqCst),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for TaskPool {
+    fn drop(&mut self) {
+        // Only the last handle tears the pool down; workers hold their own `Arc<Inner>`.
+        if Arc::strong_count(&self.inner) <= self.inner.thread_count.load(Ordering::SeqCst) + 1 {
+            let mut state = self.inner.state.lock().unwrap();
+            state.shutdown = true;
+            drop(state);
+            self.inner.available.notify_all();
+            // Release any spawners blocked waiting for a queue slot.
+            self.inner.drained.notify_all();
+        }
+    }
+}
+
+fn worker_loop(inner: Arc<Inner>) {
+    loop {
+        let mut state = inner.state.lock().unwrap();
+        loop {
+            if state.shutdown {
+                inner.thread_count.fetch_sub(1, Ordering::SeqCst);
+                return;
+            }
+            if let Some(job) = state.jobs.pop_front() {
+                drop(state);
+                // A slot just freed; wake a spawner blocked on backpressure.
+                inner.drained.notify_one();
+                run_job(&inner, job);
+                break;
+            }
+
+            // No work: if a resize dropped `max_threads` below the current worker count, retire
+            // now instead of parking.
+            if inner.thread_count.load(Ordering::SeqCst) > inner.max_threads.load(Ordering::SeqCst)
+                && try_retire(&inner)
+            {
+                return;
+            }
+
+            // Park until notified, retiring ourselves if we sit idle for too long.
+            inner.idle_count.fetch_add(1, Ordering::SeqCst);
+            let timed_out = match inner.idle_timeout {
+                Some(timeout) => {
+                    let (guard, result) =
+                        inner.available.wait_timeout(state, timeout).unwrap();
+                    state = guard;
+                    result.timed_out()
+                }
+                None => {
+                    state = inner.available.wait(state).unwrap();
+                    false
+                }
+            };
+            inner.idle_count.fetch_sub(1, Ordering::SeqCst);
+
+            // Retire if we've been idle past the timeout, or if a resize dropped `max_threads`
+            // below the current worker count.
+            let over_max = inner.thread_count.load(Ordering::SeqCst)
+                > inner.max_threads.load(Ordering::SeqCst);
+            if (timed_out || over_max) && state.jobs.is_empty() && try_retire(&inner) {
+                return;
+            }
+        }
+    }
+}
+
+/// Retire this worker if we're above `min_threads`. Returns `true` if the caller should exit.
+fn try_retire(inner: &Inner) -> bool {
+    let mut current = inner.thread_count.load(Ordering::SeqCst);
+    loop {
+        if current <= inner.min_threads.load(Ordering::SeqCst) {
+            return false;
+        }
+        match inner.thread_count.compare_exchange(
+            current,
+            current - 1,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => return true,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+fn run_job(inner: &Inner, job: Job) {
+    let result = std::panic::catch_unwind(AssertUnwindSafe(job));
+    if let Err(payload) = result {
+        match inner.panic_policy {
+            TaskPoolThreadPanicPolicy::CatchAndIgnore => {}
+            TaskPoolThreadPanicPolicy::Propagate => {
+                // This worker is about to exit, so stop counting it as alive.
+                inner.thread_count.fetch_sub(1, Ordering::SeqCst);
+                std::panic::resume_unwind(payload)
+            }
+        }
+    }
+}
+
+/// Builder for [`TaskPool`]. Mirrors the configuration surface consumed by
+/// `bevy_core::DefaultTaskPoolOptions`.
+pub struct TaskPoolBuilder {
+    min_threads: usize,
+    max_threads: usize,
+    idle_timeout: Option<Duration>,
+    capacity: Option<usize>,
+    panic_policy: TaskPoolThreadPanicPolicy,
+    thread_name: Option<String>,
+    thread_priority: Option<ThreadPriority>,
+    core_affinity: Option<CoreAffinity>,
+}
+
+impl Default for TaskPoolBuilder {
+    fn default() -> Self {
+        TaskPoolBuilder {
+            min_threads: 1,
+            max_threads: 1,
+            idle_timeout: None,
+            capacity: None,
+            panic_policy: TaskPoolThreadPanicPolicy::Propagate,
+            thread_name: None,
+            thread_priority: None,
+            core_affinity: None,
+        }
+    }
+}
+
+impl TaskPoolBuilder {
+    /// Start building a pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a fixed number of worker threads that never grow or retire.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        self.min_threads = num_threads;
+        self.max_threads = num_threads;
+        self.idle_timeout = None;
+        self
+    }
+
+    /// Use an elastic pool: keep `min` workers resident, burst up to `max` under load, and retire
+    /// on-demand workers that sit idle for longer than `idle_timeout`.
+    pub fn elastic(mut self, min: usize, max: usize, idle_timeout: Duration) -> Self {
+        self.min_threads = min;
+        self.max_threads = max.max(min);
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Bound the pool's work queue to `capacity` queued jobs. Once full, [`TaskPool::spawn`]
+    /// blocks the caller until a slot frees and [`TaskPool::try_spawn`] sheds the job. A capacity
+    /// of 0 is treated as 1 so at least one job can always be queued.
+    pub fn buffer_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = Some(capacity.max(1));
+        self
+    }
+
+    /// Set the name used for the pool's worker threads.
+    pub fn thread_name(mut self, thread_name: String) -> Self {
+        self.thread_name = Some(thread_name);
+        self
+    }
+
+    /// Set how the pool reacts to a task panicking.
+    pub fn panic_policy(mut self, panic_policy: TaskPoolThreadPanicPolicy) -> Self {
+        self.panic_policy = panic_policy;
+        self
+    }
+
+    /// Set the OS scheduling priority applied to worker threads on startup. `None` inherits the
+    /// spawning thread's priority.
+    pub fn thread_priority(mut self, thread_priority: Option<ThreadPriority>) -> Self {
+        self.thread_priority = thread_priority;
+        self
+    }
+
+    /// Set the core-affinity strategy applied to worker threads on startup. `None` lets the OS
+    /// scheduler place workers freely.
+    pub fn core_affinity(mut self, core_affinity: Option<CoreAffinity>) -> Self {
+        self.core_affinity = core_affinity;
+        self
+    }
+
+    /// Build the pool, bringing it up to `min_threads` resident workers.
+    pub fn build(self) -> TaskPool {
+        TaskPool::new(
+            self.min_threads,
+            self.max_threads,
+            self.idle_timeout,
+            self.capacity,
+            self.panic_policy,
+            self.thread_name,
+            self.thread_priority,
+            self.core_affinity,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn runs_spawned_tasks() {
+        let pool = TaskPoolBuilder::new().num_threads(2).build();
+        let task = pool.spawn(|| 20 + 22);
+        assert_eq!(task.block(), 42);
+    }
+
+    #[test]
+    fn bursts_then_retires_to_min() {
+        let pool = TaskPoolBuilder::new()
+            .elastic(1, 4, Duration::from_millis(50))
+            .build();
+        assert_eq!(pool.thread_num(), 1);
+
+        // Saturate with blocking work so the pool has to grow past min.
+        let gate = Arc::new((Mutex::new(false), Condvar::new()));
+        let started = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let gate = Arc::clone(&gate);
+                let started = Arc::clone(&started);
+                pool.spawn(move || {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    let (lock, cvar) = &*gate;
+                    let mut open = lock.lock().unwrap();
+                    while !*open {
+                        open = cvar.wait(open).unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        // Wait for the pool to spin up extra workers.
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while pool.thread_num() < 4 && Instant::now() < deadline {
+            std::thread::yield_now();
+        }
+        assert_eq!(pool.thread_num(), 4);
+
+        // Release the tasks and let the idle workers time out.
+        {
+            let (lock, cvar) = &*gate;
+            *lock.lock().unwrap() = true;
+            cvar.notify_all();
+        }
+        for task in tasks {
+            task.block();
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while pool.thread_num() > 1 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(pool.thread_num(), 1);
+    }
+
+    #[test]
+    fn try_spawn_sheds_when_queue_full() {
+        // A single worker held busy, and a one-slot queue: the first try_spawn fills the queue,
+        // the second must be shed.
+        let pool = TaskPoolBuilder::new()
+            .num_threads(1)
+            .buffer_capacity(1)
+            .build();
+
+        let gate = Arc::new((Mutex::new(false), Condvar::new()));
+        let busy = Arc::clone(&gate);
+        let hold = pool.spawn(move || {
+            let (lock, cvar) = &*busy;
+            let mut open = lock.lock().unwrap();
+            while !*open {
+                open = cvar.wait(open).unwrap();
+            }
+        });
+
+        // Wait for the worker to pick up the blocking task so the queue is empty again.
+        std::thread::sleep(Duration::from_millis(50));
+
+        // Fill the single queue slot, then the next try_spawn should be shed.
+        let queued = pool.try_spawn(|| 1u32);
+        assert!(queued.is_ok());
+        let shed = pool.try_spawn(|| 2u32);
+        assert!(shed.is_err());
+
+        // Release the worker and drain.
+        {
+            let (lock, cvar) = &*gate;
+            *lock.lock().unwrap() = true;
+            cvar.notify_all();
+        }
+        hold.block();
+        assert_eq!(queued.ok().expect("queued task was accepted").block(), 1);
+    }
+
+    #[test]
+    fn resize_elastic_grows_resident_workers() {
+        let pool = TaskPoolBuilder::new()
+            .elastic(1, 2, Duration::from_millis(50))
+            .build();
+        assert_eq!(pool.thread_num(), 1);
+
+        // Raise the floor: resident workers should be spawned immediately.
+        pool.resize_elastic(3, 5);
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while pool.thread_num() < 3 && Instant::now() < deadline {
+            std::thread::yield_now();
+        }
+        assert_eq!(pool.thread_num(), 3);
+
+        // Lower the ceiling below the current count: workers retire back down to the new min.
+        pool.resize_elastic(1, 1);
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while pool.thread_num() > 1 && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(pool.thread_num(), 1);
+    }
+}
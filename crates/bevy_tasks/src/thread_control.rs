@@ -0,0 +1,136 @@
+//! Best-effort OS thread priority and core-affinity hints applied to task-pool workers on startup.
+//!
+//! These are hints: on platforms where we can't express them they degrade to no-ops rather than
+//! failing, since getting the wrong scheduling is never fatal to correctness.
+
+/// OS scheduling priority for a pool's worker threads.
+///
+/// Maps to nice levels on Unix and `SetThreadPriority` classes on Windows. Lowering background
+/// pools below normal keeps them from stealing cycles from the frame-critical compute path.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ThreadPriority {
+    /// Run below the normal priority, yielding to normal-priority work under contention.
+    BelowNormal,
+    /// Run at the default OS priority.
+    Normal,
+    /// Run above the normal priority.
+    AboveNormal,
+}
+
+/// Strategy for pinning a pool's worker threads to CPU cores.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoreAffinity {
+    /// Let the OS scheduler place worker threads on any core.
+    Float,
+    /// Pin each worker to a distinct low-numbered core (`worker_index % core_count`), keeping
+    /// frame-critical work from being migrated off mid-frame.
+    FirstPhysicalCores,
+}
+
+/// Apply the configured priority and affinity to the current thread. Called once as each worker
+/// starts up; `worker_index` identifies the worker for per-core pinning.
+pub(crate) fn apply(priority: Option<ThreadPriority>, affinity: Option<CoreAffinity>, worker_index: usize) {
+    if let Some(priority) = priority {
+        set_current_thread_priority(priority);
+    }
+    if let Some(CoreAffinity::FirstPhysicalCores) = affinity {
+        pin_current_thread_to_core(worker_index % crate::logical_core_count().max(1));
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn set_current_thread_priority(priority: ThreadPriority) {
+    use std::os::raw::{c_int, c_uint};
+
+    extern "C" {
+        // `who == 0` targets the calling thread (Linux nice values are per-thread).
+        fn setpriority(which: c_int, who: c_uint, prio: c_int) -> c_int;
+    }
+    const PRIO_PROCESS: c_int = 0;
+
+    let nice = match priority {
+        ThreadPriority::BelowNormal => 10,
+        ThreadPriority::Normal => 0,
+        ThreadPriority::AboveNormal => -5,
+    };
+    // Safety: setpriority has no memory effects; a failure (e.g. lacking CAP_SYS_NICE to raise
+    // priority) just leaves the thread at its current nice level, which is acceptable for a hint.
+    unsafe {
+        setpriority(PRIO_PROCESS, 0, nice);
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn pin_current_thread_to_core(core: usize) {
+    use std::os::raw::{c_int, c_ulong};
+
+    // `cpu_set_t` is a bitmap; 1024 bits is the glibc default and covers any realistic core count.
+    const SETSIZE_BITS: usize = 1024;
+    const WORD_BITS: usize = std::mem::size_of::<c_ulong>() * 8;
+    const WORDS: usize = SETSIZE_BITS / WORD_BITS;
+
+    extern "C" {
+        fn sched_setaffinity(pid: c_int, cpusetsize: usize, mask: *const c_ulong) -> c_int;
+    }
+
+    if core >= SETSIZE_BITS {
+        return;
+    }
+    let mut mask = [0 as c_ulong; WORDS];
+    mask[core / WORD_BITS] |= 1 << (core % WORD_BITS);
+    // Safety: `mask` is a valid, correctly sized cpu_set_t for the duration of the call; `pid == 0`
+    // targets the calling thread.
+    unsafe {
+        sched_setaffinity(0, std::mem::size_of_val(&mask), mask.as_ptr());
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn set_current_thread_priority(priority: ThreadPriority) {
+    use std::os::raw::{c_int, c_void};
+
+    extern "system" {
+        fn GetCurrentThread() -> *mut c_void;
+        fn SetThreadPriority(thread: *mut c_void, priority: c_int) -> c_int;
+    }
+    const THREAD_PRIORITY_BELOW_NORMAL: c_int = -1;
+    const THREAD_PRIORITY_NORMAL: c_int = 0;
+    const THREAD_PRIORITY_ABOVE_NORMAL: c_int = 1;
+
+    let level = match priority {
+        ThreadPriority::BelowNormal => THREAD_PRIORITY_BELOW_NORMAL,
+        ThreadPriority::Normal => THREAD_PRIORITY_NORMAL,
+        ThreadPriority::AboveNormal => THREAD_PRIORITY_ABOVE_NORMAL,
+    };
+    // Safety: both calls operate on a pseudo-handle to the current thread and have no memory
+    // effects; a failure just leaves the default priority in place.
+    unsafe {
+        SetThreadPriority(GetCurrentThread(), level);
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn pin_current_thread_to_core(core: usize) {
+    use std::os::raw::c_void;
+
+    extern "system" {
+        fn GetCurrentThread() -> *mut c_void;
+        fn SetThreadAffinityMask(thread: *mut c_void, mask: usize) -> usize;
+    }
+
+    if core >= std::mem::size_of::<usize>() * 8 {
+        return;
+    }
+    // Safety: operates on the current-thread pseudo-handle; failure leaves affinity unchanged.
+    unsafe {
+        SetThreadAffinityMask(GetCurrentThread(), 1usize << core);
+    }
+}
+
+// Platforms without a supported priority/affinity API (e.g. macOS affinity, wasm) fall back to
+// no-ops: the hint is simply not applied.
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn set_current_thread_priority(_priority: ThreadPriority) {}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn pin_current_thread_to_core(_core: usize) {}
@@ -0,0 +1,64 @@
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::TaskPool;
+
+/// The number of logical cores the OS reports, falling back to 1 if it can't be determined.
+pub fn logical_core_count() -> usize {
+    std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1)
+}
+
+/// Defines a global, lazily-initialized task pool newtype wrapping a [`TaskPool`].
+macro_rules! taskpool {
+    ($(#[$attr:meta])* ($static:ident, $type:ident)) => {
+        static $static: OnceLock<$type> = OnceLock::new();
+
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $type(TaskPool);
+
+        impl $type {
+            /// Initialize the global pool with `f`, or return the already-initialized pool.
+            pub fn init(f: impl FnOnce() -> TaskPool) -> &'static Self {
+                $static.get_or_init(|| Self(f()))
+            }
+
+            /// Get the global pool, panicking if it has not been initialized.
+            pub fn get() -> &'static Self {
+                $static.get().expect(
+                    "the task pool has not been initialized yet; call init first",
+                )
+            }
+
+            /// Get the global pool if it has been initialized.
+            pub fn try_get() -> Option<&'static Self> {
+                $static.get()
+            }
+        }
+
+        impl Deref for $type {
+            type Target = TaskPool;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+    };
+}
+
+taskpool! {
+    /// A newtype for the global [`TaskPool`] used for most CPU-bound, frame-critical work.
+    (COMPUTE_TASK_POOL, ComputeTaskPool)
+}
+
+taskpool! {
+    /// A newtype for the global [`TaskPool`] used for CPU-bound work that can outlive a frame.
+    (ASYNC_COMPUTE_TASK_POOL, AsyncComputeTaskPool)
+}
+
+taskpool! {
+    /// A newtype for the global [`TaskPool`] used for IO-bound work such as asset loading.
+    (IO_TASK_POOL, IoTaskPool)
+}